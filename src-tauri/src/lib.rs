@@ -16,6 +16,7 @@ pub fn run() {
       transcription::get_model_status,
       transcription::download_whisper_model,
       transcription::delete_whisper_model,
+      transcription::verify_model,
       transcription::transcribe_audio,
     ])
     .setup(|app| {
@@ -1,9 +1,25 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::sync::Mutex;
-use tauri::{Manager, State};
+use tauri::{Emitter, Manager, State};
 use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
 
+// Payload for the `transcription-segment` event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TranscriptionSegmentEvent {
+    text: String,
+}
+
+// Payload for the `model-download-progress` event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ModelDownloadProgressEvent {
+    model_type: String,
+    downloaded_bytes: u64,
+    total_bytes: Option<u64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ModelStatus {
@@ -14,11 +30,68 @@ pub struct ModelStatus {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Segment {
+    pub text: String,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    // Average token probability for this segment.
+    pub confidence: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TranscriptionResult {
     pub text: String,
     pub success: bool,
+    pub detected_language: Option<String>,
+    pub segments: Vec<Segment>,
 }
 
+// Known ggml variants, most capable first. Bare names are multilingual;
+// `.en` names are English-only.
+const MODEL_VARIANTS: &[&str] = &[
+    "medium", "medium.en", "small", "small.en", "base", "base.en", "tiny", "tiny.en",
+];
+
+// Approximate published sizes per ggml variant, used to sanity-check
+// downloads (no verified SHA-256 source is available here).
+const MODEL_EXPECTED_SIZE_MB: &[(&str, f64)] = &[
+    ("tiny.en", 75.0),
+    ("tiny", 75.0),
+    ("base.en", 142.0),
+    ("base", 142.0),
+    ("small.en", 466.0),
+    ("small", 466.0),
+    ("medium.en", 1500.0),
+    ("medium", 1500.0),
+];
+
+fn expected_size_mb(model_name: &str) -> Option<f64> {
+    MODEL_EXPECTED_SIZE_MB
+        .iter()
+        .find(|(name, _)| *name == model_name)
+        .map(|(_, size)| *size)
+}
+
+fn model_size_estimate_mb(model_name: &str) -> f64 {
+    expected_size_mb(model_name).unwrap_or(0.0)
+}
+
+// Whisper.cpp's language IDs; index with `full_lang_id()` for the code.
+const WHISPER_LANGUAGES: &[&str] = &[
+    "en", "zh", "de", "es", "ru", "ko", "fr", "ja", "pt", "tr", "pl", "ca", "nl", "ar", "sv", "it",
+    "id", "hi", "fi", "vi", "he", "uk", "el", "ms", "cs", "ro", "da", "hu", "ta", "no", "th", "ur",
+    "hr", "bg", "lt", "la", "mi", "ml", "cy", "sk", "te", "fa", "lv", "bn", "sr", "az", "sl", "kn",
+    "et", "mk", "br", "eu", "is", "hy", "ne", "mn", "bs", "kk", "sq", "sw", "gl", "mr", "pa", "si",
+    "km", "sn", "yo", "so", "af", "oc", "ka", "be", "tg", "sd", "gu", "am", "yi", "lo", "uz", "fo",
+    "ht", "ps", "tk", "nn", "mt", "sa", "lb", "my", "bo", "tl", "mg", "as", "tt", "haw", "ln", "ha",
+    "ba", "jw", "su", "yue",
+];
+
+// whisper.cpp expects 16 kHz mono audio.
+const WHISPER_SAMPLE_RATE: u32 = 16000;
+
 pub struct WhisperState {
     pub context: Mutex<Option<WhisperContext>>,
     pub model_path: Mutex<Option<PathBuf>>,
@@ -59,27 +132,24 @@ pub async fn get_model_status(
     app_handle: tauri::AppHandle,
     state: State<'_, WhisperState>,
 ) -> Result<ModelStatus, String> {
-    // Check for tiny.en model
-    let tiny_path = get_model_path(&app_handle, "tiny.en")?;
-    let tiny_exists = tiny_path.exists();
-
-    // Check for base.en model
-    let base_path = get_model_path(&app_handle, "base.en")?;
-    let base_exists = base_path.exists();
-
-    // Determine which model is available
-    let (model_type, model_path, model_size_mb) = if base_exists {
-        let size = std::fs::metadata(&base_path)
-            .map(|m| m.len() as f64 / (1024.0 * 1024.0))
-            .unwrap_or(142.0);
-        (Some("base".to_string()), Some(base_path.to_string_lossy().to_string()), Some(size))
-    } else if tiny_exists {
-        let size = std::fs::metadata(&tiny_path)
-            .map(|m| m.len() as f64 / (1024.0 * 1024.0))
-            .unwrap_or(75.0);
-        (Some("tiny".to_string()), Some(tiny_path.to_string_lossy().to_string()), Some(size))
-    } else {
-        (None, None, None)
+    // Check each known variant, most capable first, and report the first one present.
+    let mut found: Option<(&str, PathBuf)> = None;
+    for variant in MODEL_VARIANTS {
+        let path = get_model_path(&app_handle, variant)?;
+        if path.exists() {
+            found = Some((variant, path));
+            break;
+        }
+    }
+
+    let (model_type, model_path, model_size_mb) = match found {
+        Some((variant, path)) => {
+            let size = std::fs::metadata(&path)
+                .map(|m| m.len() as f64 / (1024.0 * 1024.0))
+                .unwrap_or_else(|_| model_size_estimate_mb(variant));
+            (Some(variant.to_string()), Some(path.to_string_lossy().to_string()), Some(size))
+        }
+        None => (None, None, None),
     };
 
     // Update state with current model path
@@ -101,11 +171,14 @@ pub async fn download_whisper_model(
     app_handle: tauri::AppHandle,
     model_type: String,
 ) -> Result<String, String> {
-    let model_name = match model_type.as_str() {
-        "tiny" => "tiny.en",
-        "base" => "base.en",
-        _ => return Err("Invalid model type. Must be 'tiny' or 'base'".to_string()),
-    };
+    if !MODEL_VARIANTS.contains(&model_type.as_str()) {
+        return Err(format!(
+            "Invalid model type '{}'. Must be one of: {}",
+            model_type,
+            MODEL_VARIANTS.join(", ")
+        ));
+    }
+    let model_name = model_type.as_str();
 
     let model_path = get_model_path(&app_handle, model_name)?;
 
@@ -114,19 +187,28 @@ pub async fn download_whisper_model(
         return Ok(format!("Model {} already downloaded", model_type));
     }
 
-    // Hugging Face model URLs
-    let model_url = match model_name {
-        "tiny.en" => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.en.bin",
-        "base.en" => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.en.bin",
-        _ => return Err("Unknown model".to_string()),
-    };
+    // Hugging Face hosts every ggml variant under the same naming scheme.
+    let model_url = format!(
+        "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-{}.bin",
+        model_name
+    );
+    let model_url = model_url.as_str();
+
+    // Download to a `.part` file and atomically rename on completion. If a
+    // `.part` file already exists, resume it with a Range request.
+    let part_path = model_path.with_extension("bin.part");
 
-    // Download the model
-    log::info!("Downloading {} model from {}", model_name, model_url);
+    let resume_from = std::fs::metadata(&part_path).map(|m| m.len()).unwrap_or(0);
+
+    log::info!("Downloading {} model from {} (resuming from {} bytes)", model_name, model_url, resume_from);
 
     let client = reqwest::Client::new();
-    let response = client
-        .get(model_url)
+    let mut request = client.get(model_url);
+    if resume_from > 0 {
+        request = request.header("Range", format!("bytes={}-", resume_from));
+    }
+
+    let response = request
         .send()
         .await
         .map_err(|e| format!("Failed to download model: {}", e))?;
@@ -135,14 +217,67 @@ pub async fn download_whisper_model(
         return Err(format!("Failed to download model: HTTP {}", response.status()));
     }
 
-    let bytes = response
-        .bytes()
-        .await
-        .map_err(|e| format!("Failed to read model data: {}", e))?;
+    // Only treat this as a resume if the server actually honored the Range.
+    let is_resuming = resume_from > 0 && response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+
+    let content_length = response.content_length();
+    let total_bytes = match (is_resuming, content_length) {
+        (true, Some(remaining)) => Some(resume_from + remaining),
+        (false, Some(len)) => Some(len),
+        (_, None) => None,
+    };
+
+    let mut file = if is_resuming {
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&part_path)
+            .map_err(|e| format!("Failed to open partial model file: {}", e))?
+    } else {
+        std::fs::File::create(&part_path)
+            .map_err(|e| format!("Failed to create partial model file: {}", e))?
+    };
+
+    let mut downloaded: u64 = if is_resuming { resume_from } else { 0 };
+    let mut stream = response.bytes_stream();
+
+    use std::io::Write;
+    use futures_util::StreamExt;
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("Failed to read model data: {}", e))?;
+        file.write_all(&chunk)
+            .map_err(|e| format!("Failed to write model file: {}", e))?;
+        downloaded += chunk.len() as u64;
+
+        let _ = app_handle.emit(
+            "model-download-progress",
+            ModelDownloadProgressEvent {
+                model_type: model_type.clone(),
+                downloaded_bytes: downloaded,
+                total_bytes,
+            },
+        );
+    }
 
-    // Write to file
-    std::fs::write(&model_path, bytes)
-        .map_err(|e| format!("Failed to write model file: {}", e))?;
+    drop(file);
+
+    // Reject and delete the file if it's way off the expected size.
+    if let Some(expected_mb) = expected_size_mb(model_name) {
+        let actual_mb = std::fs::metadata(&part_path)
+            .map_err(|e| format!("Failed to read downloaded model for verification: {}", e))?
+            .len() as f64
+            / (1024.0 * 1024.0);
+        if (actual_mb - expected_mb).abs() / expected_mb > 0.1 {
+            let _ = std::fs::remove_file(&part_path);
+            return Err(format!(
+                "Downloaded model {} has unexpected size ({:.1} MB, expected ~{:.1} MB). The file was deleted; please retry the download.",
+                model_type, actual_mb, expected_mb
+            ));
+        }
+    }
+
+    std::fs::rename(&part_path, &model_path)
+        .map_err(|e| format!("Failed to finalize model file: {}", e))?;
 
     log::info!("Model {} downloaded successfully to {:?}", model_name, model_path);
 
@@ -155,11 +290,14 @@ pub async fn delete_whisper_model(
     state: State<'_, WhisperState>,
     model_type: String,
 ) -> Result<String, String> {
-    let model_name = match model_type.as_str() {
-        "tiny" => "tiny.en",
-        "base" => "base.en",
-        _ => return Err("Invalid model type. Must be 'tiny' or 'base'".to_string()),
-    };
+    if !MODEL_VARIANTS.contains(&model_type.as_str()) {
+        return Err(format!(
+            "Invalid model type '{}'. Must be one of: {}",
+            model_type,
+            MODEL_VARIANTS.join(", ")
+        ));
+    }
+    let model_name = model_type.as_str();
 
     let model_path = get_model_path(&app_handle, model_name)?;
 
@@ -182,6 +320,190 @@ pub async fn delete_whisper_model(
     Ok(format!("Model {} deleted successfully", model_type))
 }
 
+// Re-check an already-downloaded model's size.
+#[tauri::command]
+pub async fn verify_model(
+    app_handle: tauri::AppHandle,
+    model_type: String,
+) -> Result<String, String> {
+    if !MODEL_VARIANTS.contains(&model_type.as_str()) {
+        return Err(format!(
+            "Invalid model type '{}'. Must be one of: {}",
+            model_type,
+            MODEL_VARIANTS.join(", ")
+        ));
+    }
+    let model_name = model_type.as_str();
+    let model_path = get_model_path(&app_handle, model_name)?;
+
+    if !model_path.exists() {
+        return Err(format!("Model {} is not downloaded", model_type));
+    }
+
+    let Some(expected_mb) = expected_size_mb(model_name) else {
+        return Ok(format!("No known size for model {}, cannot verify", model_type));
+    };
+
+    let actual_mb = std::fs::metadata(&model_path)
+        .map_err(|e| format!("Failed to read model file: {}", e))?
+        .len() as f64
+        / (1024.0 * 1024.0);
+
+    if (actual_mb - expected_mb).abs() / expected_mb <= 0.1 {
+        Ok(format!("Model {} is valid", model_type))
+    } else {
+        Err(format!(
+            "Model {} has unexpected size ({:.1} MB, expected ~{:.1} MB). Delete and re-download it.",
+            model_type, actual_mb, expected_mb
+        ))
+    }
+}
+
+// Windowed-sinc (band-limited) resampler from `src_rate` to `dst_rate`.
+fn resample_to_16k(samples: &[f32], src_rate: u32, dst_rate: u32) -> Vec<f32> {
+    if src_rate == dst_rate || samples.is_empty() {
+        return samples.to_vec();
+    }
+
+    const HALF_TAPS: isize = 16;
+    let ratio = src_rate as f64 / dst_rate as f64;
+    let cutoff = 1.0_f64.min(dst_rate as f64 / src_rate as f64);
+    let out_len = ((samples.len() as f64) / ratio).round() as usize;
+
+    let sinc = |x: f64| -> f64 {
+        if x.abs() < 1e-9 {
+            1.0
+        } else {
+            let px = std::f64::consts::PI * x;
+            px.sin() / px
+        }
+    };
+
+    // Blackman window over the kernel half-width.
+    let window = |x: f64| -> f64 {
+        let n = x / HALF_TAPS as f64; // in [-1, 1]
+        0.42 + 0.5 * (std::f64::consts::PI * n).cos() + 0.08 * (2.0 * std::f64::consts::PI * n).cos()
+    };
+
+    let mut out = Vec::with_capacity(out_len);
+    for n in 0..out_len {
+        let t = n as f64 * ratio;
+        let k_start = ((t - HALF_TAPS as f64).ceil() as isize).max(0);
+        let k_end = ((t.floor() as isize + HALF_TAPS) as usize).min(samples.len().saturating_sub(1)) as isize;
+
+        let mut acc = 0.0_f64;
+        let mut weight_sum = 0.0_f64;
+        let mut k = k_start;
+        while k <= k_end {
+            let dist = t - k as f64;
+            let weight = cutoff * sinc(cutoff * dist) * window(dist);
+            acc += samples[k as usize] as f64 * weight;
+            weight_sum += weight;
+            k += 1;
+        }
+        // Near the buffer edges the kernel gets truncated by k_start/k_end,
+        // so renormalize by the weights actually summed instead of assuming
+        // the ideal kernel sum of ~1.
+        out.push(if weight_sum.abs() > 1e-9 { (acc / weight_sum) as f32 } else { 0.0 });
+    }
+
+    out
+}
+
+// 30 ms of mono i16 samples at 16 kHz, the frame size `fvad` expects.
+const VAD_FRAME_SAMPLES: usize = 480;
+// Padding kept before/after each detected speech run so word onsets and
+// offsets aren't clipped.
+const VAD_HANGOVER_MS: usize = 300;
+
+// Drop non-speech regions from a 16 kHz mono buffer before handing it to
+// whisper. Falls back to the original buffer if VAD flags the whole clip as
+// silence. Returns the trimmed buffer and the original-sample ranges kept,
+// so timestamps can be mapped back to original time.
+fn apply_vad(samples: &[f32], mode: fvad::Mode) -> (Vec<f32>, Vec<(usize, usize)>) {
+    let identity = vec![(0, samples.len())];
+
+    let i16_samples: Vec<i16> = samples
+        .iter()
+        .map(|s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
+        .collect();
+
+    let mut vad = match fvad::Fvad::new() {
+        Some(v) => v.set_mode(mode).set_sample_rate(fvad::SampleRate::Rate16kHz),
+        None => {
+            log::warn!("Failed to initialize VAD, skipping silence removal");
+            return (samples.to_vec(), identity);
+        }
+    };
+
+    let hangover_frames = (VAD_HANGOVER_MS / 30).max(1);
+    let num_frames = (i16_samples.len() + VAD_FRAME_SAMPLES - 1) / VAD_FRAME_SAMPLES;
+    let mut is_speech = vec![false; num_frames];
+    let mut any_classified_speech = false;
+
+    for (i, frame) in i16_samples.chunks(VAD_FRAME_SAMPLES).enumerate() {
+        // A trailing frame shorter than 30ms can't be classified by fvad;
+        // keep it as speech rather than silently dropping that audio.
+        is_speech[i] = if frame.len() == VAD_FRAME_SAMPLES {
+            let speech = vad.is_voice_frame(frame).unwrap_or(true);
+            any_classified_speech |= speech;
+            speech
+        } else {
+            true
+        };
+    }
+
+    // Extend each speech run by the hangover window so onsets/offsets survive.
+    let mut keep = vec![false; is_speech.len()];
+    for (i, &speech) in is_speech.iter().enumerate() {
+        if speech {
+            let start = i.saturating_sub(hangover_frames);
+            let end = (i + hangover_frames).min(keep.len().saturating_sub(1));
+            for k in keep.iter_mut().take(end + 1).skip(start) {
+                *k = true;
+            }
+        }
+    }
+
+    if !any_classified_speech {
+        log::info!("VAD flagged entire clip as silence, transcribing original audio");
+        return (samples.to_vec(), identity);
+    }
+
+    let mut retained = Vec::with_capacity(samples.len());
+    let mut kept_ranges = Vec::new();
+    for (i, &keep_frame) in keep.iter().enumerate() {
+        if keep_frame {
+            let start = i * VAD_FRAME_SAMPLES;
+            let end = (start + VAD_FRAME_SAMPLES).min(samples.len());
+            retained.extend_from_slice(&samples[start..end]);
+            kept_ranges.push((start, end));
+        }
+    }
+
+    log::info!("VAD retained {} of {} samples", retained.len(), samples.len());
+    (retained, kept_ranges)
+}
+
+// Map a sample offset in the VAD-trimmed buffer back to the original buffer.
+fn trimmed_to_original_sample(trimmed_sample: usize, kept_ranges: &[(usize, usize)]) -> usize {
+    let mut remaining = trimmed_sample;
+    for &(start, end) in kept_ranges {
+        let len = end - start;
+        if remaining < len {
+            return start + remaining;
+        }
+        remaining -= len;
+    }
+    kept_ranges.last().map(|&(_, end)| end).unwrap_or(0)
+}
+
+fn remap_trimmed_ms_to_original(trimmed_ms: i64, kept_ranges: &[(usize, usize)]) -> i64 {
+    let trimmed_sample = (trimmed_ms * WHISPER_SAMPLE_RATE as i64 / 1000).max(0) as usize;
+    let original_sample = trimmed_to_original_sample(trimmed_sample, kept_ranges);
+    (original_sample as i64) * 1000 / WHISPER_SAMPLE_RATE as i64
+}
+
 fn load_whisper_context(model_path: &PathBuf) -> Result<WhisperContext, String> {
     log::info!("Loading Whisper model from {:?}", model_path);
 
@@ -196,7 +518,21 @@ pub async fn transcribe_audio(
     app_handle: tauri::AppHandle,
     state: State<'_, WhisperState>,
     audio_data: Vec<u8>,
+    language: Option<String>,
+    translate: Option<bool>,
+    enable_vad: Option<bool>,
+    vad_mode: Option<u8>,
 ) -> Result<TranscriptionResult, String> {
+    // `None`/`"auto"` let whisper detect the spoken language itself.
+    let language = language.filter(|lang| lang != "auto");
+    let translate = translate.unwrap_or(false);
+    let enable_vad = enable_vad.unwrap_or(true);
+    let vad_mode = match vad_mode.unwrap_or(2) {
+        0 => fvad::Mode::Quality,
+        1 => fvad::Mode::LowBitrate,
+        3 => fvad::Mode::VeryAggressive,
+        _ => fvad::Mode::Aggressive,
+    };
     log::info!("Transcribing audio, size: {} bytes", audio_data.len());
 
     // Get model status
@@ -255,23 +591,54 @@ pub async fn transcribe_audio(
     log::info!("Audio specs: {} Hz, {} channels, {} samples",
                spec.sample_rate, spec.channels, mono_samples.len());
 
+    // Whisper requires exactly 16 kHz mono input; resample anything else.
+    let whisper_samples: Vec<f32> = if spec.sample_rate != WHISPER_SAMPLE_RATE {
+        log::info!("Resampling audio from {} Hz to {} Hz", spec.sample_rate, WHISPER_SAMPLE_RATE);
+        resample_to_16k(&mono_samples, spec.sample_rate, WHISPER_SAMPLE_RATE)
+    } else {
+        mono_samples
+    };
+
+    // Skip silent regions before transcribing, unless disabled.
+    let (whisper_samples, vad_kept_ranges) = if enable_vad {
+        apply_vad(&whisper_samples, vad_mode)
+    } else {
+        let identity = vec![(0, whisper_samples.len())];
+        (whisper_samples, identity)
+    };
+
     // Create parameters for transcription
     let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
 
-    // Configure for English-only
-    params.set_language(Some("en"));
-    params.set_translate(false);
+    // `None` tells whisper to auto-detect the spoken language.
+    params.set_language(language.as_deref());
+    params.set_translate(translate);
     params.set_print_special(false);
     params.set_print_progress(false);
     params.set_print_realtime(false);
     params.set_print_timestamps(false);
 
+    // Report progress to the frontend so long recordings don't look stuck.
+    let progress_app_handle = app_handle.clone();
+    params.set_progress_callback_safe(move |progress| {
+        let _ = progress_app_handle.emit("transcription-progress", progress);
+    });
+
+    // Stream each segment's text to the frontend as it's decoded.
+    let segment_app_handle = app_handle.clone();
+    params.set_segment_callback_safe(move |segment: whisper_rs::SegmentCallbackData| {
+        let _ = segment_app_handle.emit(
+            "transcription-segment",
+            TranscriptionSegmentEvent { text: segment.text },
+        );
+    });
+
     // Run transcription
     let mut state_obj = ctx.create_state()
         .map_err(|e| format!("Failed to create Whisper state: {}", e))?;
 
     state_obj
-        .full(params, &mono_samples)
+        .full(params, &whisper_samples)
         .map_err(|e| format!("Failed to run transcription: {}", e))?;
 
     // Get number of segments
@@ -281,21 +648,59 @@ pub async fn transcribe_audio(
 
     log::info!("Transcription complete, {} segments", num_segments);
 
-    // Extract text from all segments
+    // Extract text, timestamps and confidence from all segments.
     let mut full_text = String::new();
+    let mut segments = Vec::with_capacity(num_segments as usize);
     for i in 0..num_segments {
-        let segment = state_obj
+        let segment_text = state_obj
             .full_get_segment_text(i)
             .map_err(|e| format!("Failed to get segment text: {}", e))?;
-        full_text.push_str(&segment);
+        full_text.push_str(&segment_text);
         full_text.push(' ');
+
+        // t0/t1 are in 10ms units of the VAD-trimmed buffer; remap to original time.
+        let start_ms = state_obj
+            .full_get_segment_t0(i)
+            .map_err(|e| format!("Failed to get segment start: {}", e))?
+            * 10;
+        let end_ms = state_obj
+            .full_get_segment_t1(i)
+            .map_err(|e| format!("Failed to get segment end: {}", e))?
+            * 10;
+        let start_ms = remap_trimmed_ms_to_original(start_ms, &vad_kept_ranges);
+        let end_ms = remap_trimmed_ms_to_original(end_ms, &vad_kept_ranges);
+
+        let num_tokens = state_obj.full_n_tokens(i).unwrap_or(0);
+        let confidence = if num_tokens > 0 {
+            let sum: f32 = (0..num_tokens)
+                .filter_map(|t| state_obj.full_get_token_prob(i, t).ok())
+                .sum();
+            sum / num_tokens as f32
+        } else {
+            0.0
+        };
+
+        segments.push(Segment {
+            text: segment_text,
+            start_ms,
+            end_ms,
+            confidence,
+        });
     }
 
     let final_text = full_text.trim().to_string();
     log::info!("Transcription result: {}", final_text);
 
+    let detected_language = state_obj
+        .full_lang_id()
+        .ok()
+        .and_then(|id| WHISPER_LANGUAGES.get(id as usize))
+        .map(|code| code.to_string());
+
     Ok(TranscriptionResult {
         text: final_text,
         success: true,
+        detected_language,
+        segments,
     })
 }